@@ -0,0 +1,149 @@
+use crate::level::{FilterLevel, LogLevel};
+
+/// A single `target=level` directive parsed out of a `--filter`/`RUST_LOG`
+/// spec, e.g. `mymod::net=trace`.
+#[derive(Debug, Clone)]
+struct Directive {
+    target: String,
+    level: FilterLevel,
+}
+
+/// A parsed `RUST_LOG`-style filter spec: per-target threshold directives
+/// plus an optional bare default level.
+#[derive(Debug, Clone, Default)]
+pub struct Directives {
+    default: Option<FilterLevel>,
+    rules: Vec<Directive>,
+}
+
+impl Directives {
+    /// Parse a comma-separated directive spec such as
+    /// `"mymod=debug,mymod::net=trace,error"`.
+    pub fn parse(spec: &str) -> Self {
+        let mut directives = Directives::default();
+
+        for part in spec.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+
+            match part.split_once('=') {
+                Some((target, level)) => {
+                    if let Some(level) = FilterLevel::parse_name(level.trim()) {
+                        directives.rules.push(Directive {
+                            target: target.trim().to_string(),
+                            level,
+                        });
+                    }
+                }
+                None => {
+                    // A bare directive is either a default level (no `=`)
+                    // or a target with no level, which enables every level
+                    // for that target.
+                    if let Some(level) = FilterLevel::parse_name(part) {
+                        directives.default = Some(level);
+                    } else {
+                        directives.rules.push(Directive {
+                            target: part.to_string(),
+                            level: FilterLevel::Trace,
+                        });
+                    }
+                }
+            }
+        }
+
+        directives
+    }
+
+    /// Whether a line with the given target and level passes this filter.
+    /// The longest directive target that prefixes `target` wins; if none
+    /// match, the bare default level applies (everything passes if there is
+    /// no default at all).
+    pub fn admits(&self, target: Option<&str>, level: Option<LogLevel>) -> bool {
+        let best_match = target.and_then(|target| {
+            self.rules
+                .iter()
+                .filter(|rule| target.starts_with(rule.target.as_str()))
+                .max_by_key(|rule| rule.target.len())
+        });
+
+        match best_match.map(|rule| rule.level).or(self.default) {
+            Some(filter_level) => filter_level.admits(level),
+            None => true,
+        }
+    }
+}
+
+/// Pull a module-path-like target (e.g. `mymod::net`) out of a log line:
+/// the first whitespace-separated token that looks like a logger/module
+/// name. Falls back to `None` when nothing matches.
+pub fn extract_target(line: &str) -> Option<String> {
+    line.split_whitespace().find_map(|token| {
+        // Trim leading/trailing punctuation (e.g. the trailing ':' after
+        // "mymod::net:") without touching the interior "::" separators.
+        let trimmed = token.trim_matches(|c: char| !c.is_alphanumeric() && c != '_');
+        let is_target_like = !trimmed.is_empty()
+            && trimmed.contains("::")
+            && trimmed
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == ':' || c == '_');
+        is_target_like.then(|| trimmed.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_matching_target_wins() {
+        let directives = Directives::parse("mymod=debug,mymod::net=trace,error");
+
+        // "mymod::net" matches both "mymod" and "mymod::net"; the longer,
+        // more specific rule should win.
+        assert!(directives.admits(Some("mymod::net"), Some(LogLevel::Trace)));
+        // "mymod::other" only matches the shorter "mymod" rule (threshold: debug).
+        assert!(!directives.admits(Some("mymod::other"), Some(LogLevel::Trace)));
+        assert!(directives.admits(Some("mymod::other"), Some(LogLevel::Debug)));
+    }
+
+    #[test]
+    fn unmatched_target_falls_back_to_the_bare_default() {
+        let directives = Directives::parse("mymod=debug,error");
+
+        assert!(directives.admits(Some("othermod"), Some(LogLevel::Error)));
+        assert!(!directives.admits(Some("othermod"), Some(LogLevel::Warn)));
+    }
+
+    #[test]
+    fn no_default_and_no_match_admits_everything() {
+        let directives = Directives::parse("mymod=error");
+
+        assert!(directives.admits(Some("othermod"), Some(LogLevel::Trace)));
+        assert!(directives.admits(None, Some(LogLevel::Trace)));
+    }
+
+    #[test]
+    fn bare_target_with_no_level_enables_every_level() {
+        let directives = Directives::parse("mymod");
+
+        assert!(directives.admits(Some("mymod"), Some(LogLevel::Trace)));
+    }
+
+    #[test]
+    fn no_target_extracted_uses_the_bare_default_only() {
+        let directives = Directives::parse("mymod::net=trace,warn");
+
+        assert!(directives.admits(None, Some(LogLevel::Warn)));
+        assert!(!directives.admits(None, Some(LogLevel::Info)));
+    }
+
+    #[test]
+    fn extract_target_strips_trailing_punctuation() {
+        assert_eq!(
+            extract_target("INFO mymod::net: something happened"),
+            Some("mymod::net".to_string())
+        );
+    }
+}
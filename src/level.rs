@@ -0,0 +1,183 @@
+use clap::ValueEnum;
+use colored::*;
+use serde::Serialize;
+
+/// Severity of a single parsed log line, ordered from least to most severe.
+#[derive(ValueEnum, Clone, Debug, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
+#[serde(rename_all = "lowercase")]
+#[repr(usize)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Critical,
+}
+
+impl LogLevel {
+    pub fn colorize(&self, s: &str) -> ColoredString {
+        match self {
+            Self::Trace => s.magenta(),
+            Self::Debug => s.blue(),
+            Self::Info => s.green(),
+            Self::Warn => s.yellow().bold(),
+            Self::Error => s.red().bold(),
+            Self::Critical => s.white().on_red().bold(),
+        }
+    }
+
+    /// Parse a level name as it would appear in a log line or a capture
+    /// group, case-insensitively.
+    pub fn parse_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "trace" | "trac" => Some(Self::Trace),
+            "debug" | "debu" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" | "erro" => Some(Self::Error),
+            "critical" | "crit" | "fatal" | "fata" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+}
+
+impl From<LogLevel> for FilterLevel {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => Self::Trace,
+            LogLevel::Debug => Self::Debug,
+            LogLevel::Info => Self::Info,
+            LogLevel::Warn => Self::Warn,
+            LogLevel::Error => Self::Error,
+            LogLevel::Critical => Self::Critical,
+        }
+    }
+}
+
+/// The `--level` flag: either a minimum severity threshold or `Off` to
+/// suppress all level-filtered output.
+#[derive(ValueEnum, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum FilterLevel {
+    Off,
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Critical,
+}
+
+impl FilterLevel {
+    /// The minimum `LogLevel` this filter admits, or `None` if it admits nothing.
+    pub fn threshold(&self) -> Option<LogLevel> {
+        match self {
+            Self::Off => None,
+            Self::Trace => Some(LogLevel::Trace),
+            Self::Debug => Some(LogLevel::Debug),
+            Self::Info => Some(LogLevel::Info),
+            Self::Warn => Some(LogLevel::Warn),
+            Self::Error => Some(LogLevel::Error),
+            Self::Critical => Some(LogLevel::Critical),
+        }
+    }
+
+    /// Whether `level` passes this threshold (`Off` admits nothing).
+    pub fn admits(&self, level: Option<LogLevel>) -> bool {
+        match self.threshold() {
+            None => false,
+            Some(threshold) => matches!(level, Some(l) if l >= threshold),
+        }
+    }
+
+    /// Parse a level name as used in directive strings (`RUST_LOG`-style),
+    /// case-insensitively. This is distinct from the `ValueEnum` parser clap
+    /// uses for `--level`, which only accepts clap's own casing rules.
+    pub fn parse_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("off") {
+            return Some(Self::Off);
+        }
+        LogLevel::parse_name(name).map(Self::from)
+    }
+}
+
+pub fn parse_log_level(line: &str) -> Option<LogLevel> {
+    // Simple fast check: check for common level strings within the line
+    // In a real production tool, this might extract the level from a specific column
+    let upper = line.to_uppercase();
+    if upper.contains("CRITICAL") || upper.contains("CRIT") || upper.contains("FATAL") {
+        Some(LogLevel::Critical)
+    } else if upper.contains("ERROR") || upper.contains("ERRO") {
+        Some(LogLevel::Error)
+    } else if upper.contains("WARN") {
+        Some(LogLevel::Warn)
+    } else if upper.contains("INFO") {
+        Some(LogLevel::Info)
+    } else if upper.contains("DEBUG") {
+        Some(LogLevel::Debug)
+    } else if upper.contains("TRACE") {
+        Some(LogLevel::Trace)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_is_ordered_by_discriminant() {
+        assert!(LogLevel::Critical > LogLevel::Error);
+        assert!(LogLevel::Error > LogLevel::Warn);
+        assert!(LogLevel::Warn > LogLevel::Info);
+        assert!(LogLevel::Info > LogLevel::Debug);
+        assert!(LogLevel::Debug > LogLevel::Trace);
+    }
+
+    #[test]
+    fn filter_level_off_admits_nothing() {
+        assert!(!FilterLevel::Off.admits(Some(LogLevel::Critical)));
+        assert!(!FilterLevel::Off.admits(None));
+    }
+
+    #[test]
+    fn filter_level_is_a_threshold() {
+        assert!(FilterLevel::Warn.admits(Some(LogLevel::Warn)));
+        assert!(FilterLevel::Warn.admits(Some(LogLevel::Error)));
+        assert!(FilterLevel::Warn.admits(Some(LogLevel::Critical)));
+        assert!(!FilterLevel::Warn.admits(Some(LogLevel::Info)));
+        assert!(!FilterLevel::Warn.admits(None));
+    }
+
+    #[test]
+    fn log_level_parse_name_recognizes_abbreviations() {
+        assert_eq!(LogLevel::parse_name("CRIT"), Some(LogLevel::Critical));
+        assert_eq!(LogLevel::parse_name("FATAL"), Some(LogLevel::Critical));
+        assert_eq!(LogLevel::parse_name("ERRO"), Some(LogLevel::Error));
+    }
+
+    #[test]
+    fn filter_level_parse_name_recognizes_off_and_abbreviations() {
+        assert_eq!(FilterLevel::parse_name("off"), Some(FilterLevel::Off));
+        assert_eq!(FilterLevel::parse_name("OFF"), Some(FilterLevel::Off));
+        assert_eq!(FilterLevel::parse_name("crit"), Some(FilterLevel::Critical));
+        assert_eq!(FilterLevel::parse_name("bogus"), None);
+    }
+
+    #[test]
+    fn parse_log_level_recognizes_abbreviations() {
+        assert_eq!(
+            parse_log_level("2026-01-01 CRIT disk full"),
+            Some(LogLevel::Critical)
+        );
+        assert_eq!(
+            parse_log_level("a FATAL error occurred"),
+            Some(LogLevel::Critical)
+        );
+        assert_eq!(
+            parse_log_level("time=2026 level=ERRO msg=down"),
+            Some(LogLevel::Error)
+        );
+    }
+}
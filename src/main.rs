@@ -1,139 +1,207 @@
-use anyhow::{Context, Result};
-use clap::{Parser, ValueEnum};
+mod directives;
+mod input;
+mod level;
+mod output;
+mod parse;
+mod time;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
 use colored::*;
+use directives::Directives;
+use input::Source;
+use level::{FilterLevel, LogLevel};
+use output::OutputFormat;
+use parse::{InputFormat, LineParser};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::env;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use time::{Bucketer, TimeRange};
+
+/// Minimum time between summary reprints while `--follow`ing. The check runs
+/// as each line is processed, so no reprint happens while no new lines arrive.
+const FOLLOW_SUMMARY_INTERVAL: Duration = Duration::from_secs(2);
 
 /// A lightweight, efficient command-line log analyzer.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Path to the log file to analyze
+    /// Path to the log file to analyze. Omit, or pass "-", to read from stdin.
     #[arg(short, long)]
-    file: PathBuf,
+    file: Option<PathBuf>,
+
+    /// Keep the input open and process newly appended lines like `tail -f`,
+    /// periodically refreshing the summary.
+    #[arg(long)]
+    follow: bool,
 
-    /// Filter logs by level
+    /// Filter logs by minimum severity (keeps this level and anything more severe)
     #[arg(short, long)]
-    level: Option<LogLevelFilter>,
+    level: Option<FilterLevel>,
 
     /// Search for a specific keyword in the log messages
     #[arg(short, long)]
     search: Option<String>,
-}
-
-#[derive(ValueEnum, Clone, Debug, PartialEq, Eq, Hash, Copy)]
-enum LogLevelFilter {
-    Error,
-    Warn,
-    Info,
-    Debug,
-    Trace,
-}
-
-impl LogLevelFilter {
-    fn colorize(&self, s: &str) -> ColoredString {
-        match self {
-            Self::Error => s.red().bold(),
-            Self::Warn => s.yellow().bold(),
-            Self::Info => s.green(),
-            Self::Debug => s.blue(),
-            Self::Trace => s.magenta(),
-        }
-    }
-}
 
-fn parse_log_level(line: &str) -> Option<LogLevelFilter> {
-    // Simple fast check: check for common level strings within the line
-    // In a real production tool, this might extract the level from a specific column
-    let upper = line.to_uppercase();
-    if upper.contains("ERROR") {
-        Some(LogLevelFilter::Error)
-    } else if upper.contains("WARN") {
-        Some(LogLevelFilter::Warn)
-    } else if upper.contains("INFO") {
-        Some(LogLevelFilter::Info)
-    } else if upper.contains("DEBUG") {
-        Some(LogLevelFilter::Debug)
-    } else if upper.contains("TRACE") {
-        Some(LogLevelFilter::Trace)
-    } else {
-        None
-    }
+    /// RUST_LOG-style per-target filter directives, e.g.
+    /// "mymod=debug,mymod::net=trace,error". Falls back to the RUST_LOG
+    /// environment variable when not given.
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Regular expression with named capture groups (ts, level, target, msg)
+    /// used to extract fields from each line, e.g.
+    /// '^(?P<ts>\S+ \S+) (?P<level>\w+) (?P<target>[\w:]+): (?P<msg>.*)$'.
+    /// Falls back to the built-in heuristics when omitted or a line doesn't match.
+    #[arg(long)]
+    pattern: Option<String>,
+
+    /// How to interpret each input line
+    #[arg(long, value_enum, default_value = "auto")]
+    input_format: InputFormat,
+
+    /// JSON object key to read the level from (only used in json/auto input format)
+    #[arg(long, default_value = "level")]
+    level_key: String,
+
+    /// How to render matched lines and the summary
+    #[arg(long, value_enum, default_value = "pretty")]
+    output_format: OutputFormat,
+
+    /// Only keep lines timestamped at or after this instant (RFC3339 or a
+    /// common log timestamp format)
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Only keep lines timestamped at or before this instant (RFC3339 or a
+    /// common log timestamp format)
+    #[arg(long)]
+    until: Option<String>,
+
+    /// Group matched lines into buckets of this size (e.g. "5m", "1h") and
+    /// print a per-bucket breakdown by level
+    #[arg(long)]
+    bucket: Option<String>,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
-    let file = File::open(&args.file)
-        .with_context(|| format!("Failed to open log file at {:?}", args.file))?;
-    let reader = BufReader::new(file);
-
-    let mut level_counts: HashMap<LogLevelFilter, usize> = HashMap::new();
+    let directives = args
+        .filter
+        .clone()
+        .or_else(|| env::var("RUST_LOG").ok())
+        .map(|spec| Directives::parse(&spec));
+
+    let parser = LineParser::new(args.input_format, args.pattern.as_deref(), args.level_key)?;
+
+    let time_range = TimeRange {
+        since: args
+            .since
+            .as_deref()
+            .map(|s| time::parse_timestamp(s).ok_or_else(|| anyhow!("Invalid --since {s:?}")))
+            .transpose()?,
+        until: args
+            .until
+            .as_deref()
+            .map(|s| time::parse_timestamp(s).ok_or_else(|| anyhow!("Invalid --until {s:?}")))
+            .transpose()?,
+    };
+    let mut bucketer = args
+        .bucket
+        .as_deref()
+        .map(time::parse_duration)
+        .transpose()?
+        .map(Bucketer::new);
+
+    let source = Source::from_arg(args.file.as_deref());
+    let reader = source.open()?;
+
+    let mut level_counts: HashMap<LogLevel, usize> = HashMap::new();
     let mut total_lines = 0;
     let mut matched_lines = 0;
+    let mut last_summary = Instant::now();
 
     let search_term = args.search.as_deref().map(|s| s.to_lowercase());
+    let filters_active = args.level.is_some()
+        || args.search.is_some()
+        || directives.is_some()
+        || time_range.is_active();
 
-    println!("{}", "Starting log analysis...".cyan().bold());
+    if args.output_format == OutputFormat::Pretty {
+        println!("{}", "Starting log analysis...".cyan().bold());
+    }
 
-    for line_result in reader.lines() {
-        let line = line_result.context("Failed to read a line from the file")?;
+    input::for_each_line(reader, args.follow, |line| {
         total_lines += 1;
 
-        let level = parse_log_level(&line);
+        let parsed = parser.parse(line);
+        let timestamp = parsed.timestamp.as_deref().and_then(time::parse_timestamp);
 
         // Update statistics
-        if let Some(l) = level {
+        if let Some(l) = parsed.level {
             *level_counts.entry(l).or_insert(0) += 1;
         }
 
-        // Apply level filter
-        if let Some(filter_level) = &args.level {
-            if Some(*filter_level) != level {
-                continue;
+        // Apply level filter: keep lines at or above the threshold
+        let admitted = args
+            .level
+            .as_ref()
+            .is_none_or(|filter_level| filter_level.admits(parsed.level))
+            // Apply per-target RUST_LOG-style directives
+            && directives
+                .as_ref()
+                .is_none_or(|d| d.admits(parsed.target.as_deref(), parsed.level))
+            // Apply keyword search
+            && search_term
+                .as_ref()
+                .is_none_or(|search| parsed.message.to_lowercase().contains(search))
+            // Apply --since/--until
+            && time_range.admits(timestamp);
+
+        if admitted {
+            matched_lines += 1;
+
+            if let (Some(bucketer), Some(timestamp)) = (bucketer.as_mut(), timestamp) {
+                bucketer.record(timestamp, parsed.level);
             }
-        }
 
-        // Apply keyword search
-        if let Some(ref search) = search_term {
-            if !line.to_lowercase().contains(search) {
-                continue;
+            // Print the matching log lines if filters are active (pretty
+            // mode), or always (json mode, so it streams one record per
+            // line regardless of whether any filter narrowed the output).
+            if filters_active || args.output_format == OutputFormat::Json {
+                output::print_matched_line(args.output_format, line, &parsed);
             }
         }
 
-        matched_lines += 1;
-
-        // Print the matching log lines if filters are active
-        if args.level.is_some() || args.search.is_some() {
-            if let Some(l) = level {
-                println!("{}", l.colorize(&line));
-            } else {
-                println!("{}", line);
-            }
+        // While following, periodically refresh the summary instead of
+        // only printing it once at EOF (which may never come).
+        if args.follow && last_summary.elapsed() >= FOLLOW_SUMMARY_INTERVAL {
+            output::print_summary(
+                args.output_format,
+                total_lines,
+                matched_lines,
+                filters_active,
+                level_counts.clone(),
+            );
+            last_summary = Instant::now();
         }
-    }
 
-    // Summary Output
-    println!("\n{}", "--- Log Analysis Summary ---".cyan().bold());
-    println!("Total lines processed: {}", total_lines);
-    
-    if args.level.is_some() || args.search.is_some() {
-        println!("Lines matching filters: {}", matched_lines);
-    }
+        Ok(())
+    })?;
+
+    output::print_summary(
+        args.output_format,
+        total_lines,
+        matched_lines,
+        filters_active,
+        level_counts,
+    );
 
-    println!("\n{}", "Log Level Counts:".bold());
-    let mut counts_vec: Vec<_> = level_counts.into_iter().collect();
-    // Sort by descending count
-    counts_vec.sort_by(|a, b| b.1.cmp(&a.1));
-
-    if counts_vec.is_empty() {
-        println!("  No recognizable log levels found.");
-    } else {
-        for (level, count) in counts_vec {
-            let level_str = format!("{:?}", level).to_uppercase();
-            println!("  {:<8}: {}", level.colorize(&level_str), count);
+    if args.output_format == OutputFormat::Pretty {
+        if let Some(bucketer) = &bucketer {
+            bucketer.render();
         }
     }
 
@@ -0,0 +1,168 @@
+use crate::level::LogLevel;
+use crate::parse::ParsedLine;
+use clap::ValueEnum;
+use colored::*;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// How to render matched lines and the final summary.
+#[derive(ValueEnum, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum OutputFormat {
+    /// Human-readable, colorized output (the default).
+    Pretty,
+    /// One JSON object per matched line, and a JSON summary, for piping
+    /// into other tools.
+    Json,
+}
+
+#[derive(Serialize)]
+struct MatchedRecord<'a> {
+    timestamp: Option<&'a str>,
+    level: Option<LogLevel>,
+    target: Option<&'a str>,
+    message: &'a str,
+}
+
+#[derive(Serialize)]
+struct Summary {
+    total_lines: usize,
+    matched_lines: usize,
+    level_counts: HashMap<String, usize>,
+}
+
+pub fn print_matched_line(format: OutputFormat, raw_line: &str, parsed: &ParsedLine) {
+    match format {
+        OutputFormat::Pretty => match parsed.level {
+            Some(l) => println!("{}", l.colorize(raw_line)),
+            None => println!("{}", raw_line),
+        },
+        OutputFormat::Json => {
+            if let Some(json) = matched_record_json(parsed) {
+                println!("{}", json);
+            }
+        }
+    }
+}
+
+/// Compact, single-line JSON for one matched line, so a stream of records
+/// can be consumed one per line by another tool.
+fn matched_record_json(parsed: &ParsedLine) -> Option<String> {
+    let record = MatchedRecord {
+        timestamp: parsed.timestamp.as_deref(),
+        level: parsed.level,
+        target: parsed.target.as_deref(),
+        message: &parsed.message,
+    };
+    serde_json::to_string(&record).ok()
+}
+
+pub fn print_summary(
+    format: OutputFormat,
+    total_lines: usize,
+    matched_lines: usize,
+    filters_active: bool,
+    level_counts: HashMap<LogLevel, usize>,
+) {
+    match format {
+        OutputFormat::Pretty => {
+            println!("\n{}", "--- Log Analysis Summary ---".cyan().bold());
+            println!("Total lines processed: {}", total_lines);
+
+            if filters_active {
+                println!("Lines matching filters: {}", matched_lines);
+            }
+
+            println!("\n{}", "Log Level Counts:".bold());
+            let mut counts_vec: Vec<_> = level_counts.into_iter().collect();
+            // Sort by descending count
+            counts_vec.sort_by(|a, b| b.1.cmp(&a.1));
+
+            if counts_vec.is_empty() {
+                println!("  No recognizable log levels found.");
+            } else {
+                for (level, count) in counts_vec {
+                    let level_str = format!("{:?}", level).to_uppercase();
+                    println!("  {:<8}: {}", level.colorize(&level_str), count);
+                }
+            }
+        }
+        OutputFormat::Json => {
+            if let Some(json) = summary_json(total_lines, matched_lines, level_counts) {
+                println!("{}", json);
+            }
+        }
+    }
+}
+
+/// Compact, single-line JSON for the summary, matching the matched-record
+/// output above: `--output-format json` is meant to be piped line by line,
+/// and a pretty-printed block would break that.
+fn summary_json(
+    total_lines: usize,
+    matched_lines: usize,
+    level_counts: HashMap<LogLevel, usize>,
+) -> Option<String> {
+    let level_counts = level_counts
+        .into_iter()
+        .map(|(level, count)| (format!("{:?}", level).to_lowercase(), count))
+        .collect();
+    let summary = Summary {
+        total_lines,
+        matched_lines,
+        level_counts,
+    };
+    serde_json::to_string(&summary).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_json_is_single_line() {
+        let mut level_counts = HashMap::new();
+        level_counts.insert(LogLevel::Error, 2);
+        level_counts.insert(LogLevel::Info, 5);
+
+        let json = summary_json(10, 7, level_counts).unwrap();
+
+        assert_eq!(json.lines().count(), 1);
+        assert!(json.contains("\"total_lines\":10"));
+        assert!(json.contains("\"matched_lines\":7"));
+    }
+
+    #[test]
+    fn matched_record_json_is_single_line() {
+        let parsed = ParsedLine {
+            timestamp: Some("2026-01-01T00:00:00Z".to_string()),
+            level: Some(LogLevel::Warn),
+            target: Some("mymod::net".to_string()),
+            message: "disk almost full".to_string(),
+        };
+
+        let json = matched_record_json(&parsed).unwrap();
+
+        assert_eq!(json.lines().count(), 1);
+        assert!(json.contains("\"level\":\"warn\""));
+        assert!(json.contains("\"message\":\"disk almost full\""));
+    }
+
+    #[test]
+    fn json_format_reads_the_configured_level_key() {
+        use crate::parse::{InputFormat, LineParser};
+
+        let parser = LineParser::new(InputFormat::Json, None, "severity".to_string()).unwrap();
+        let parsed = parser.parse(r#"{"severity":"warn","msg":"low disk space"}"#);
+        assert_eq!(parsed.level, Some(LogLevel::Warn));
+        assert_eq!(parsed.message, "low disk space");
+    }
+
+    #[test]
+    fn auto_format_falls_back_to_text_for_non_json_lines() {
+        use crate::parse::{InputFormat, LineParser};
+
+        let parser = LineParser::new(InputFormat::Auto, None, "level".to_string()).unwrap();
+        let parsed = parser.parse("plain text ERROR line");
+        assert_eq!(parsed.level, Some(LogLevel::Error));
+    }
+}
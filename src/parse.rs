@@ -0,0 +1,188 @@
+use crate::directives;
+use crate::level::{self, LogLevel};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use regex::Regex;
+
+/// The fields pulled out of a single log line, whether by JSON decoding,
+/// the regex pattern, or the default heuristics.
+#[derive(Debug, Clone)]
+pub struct ParsedLine {
+    pub timestamp: Option<String>,
+    pub level: Option<LogLevel>,
+    pub target: Option<String>,
+    pub message: String,
+}
+
+/// How to interpret each input line.
+#[derive(ValueEnum, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum InputFormat {
+    /// Try JSON first, falling back to text parsing for lines that aren't
+    /// a JSON object.
+    Auto,
+    Text,
+    Json,
+}
+
+/// Parses log lines as JSON or using a user-supplied regular expression
+/// with named capture groups (`ts`, `level`, `target`, `msg`), falling back
+/// to the built-in heuristics when no pattern is configured or a line
+/// doesn't match.
+pub struct LineParser {
+    format: InputFormat,
+    pattern: Option<Regex>,
+    level_key: String,
+}
+
+impl LineParser {
+    /// Compiles `pattern` once up front so the read loop never pays for
+    /// regex compilation per line.
+    pub fn new(format: InputFormat, pattern: Option<&str>, level_key: String) -> Result<Self> {
+        let pattern = pattern
+            .map(Regex::new)
+            .transpose()
+            .context("Invalid --pattern regular expression")?;
+        Ok(Self {
+            format,
+            pattern,
+            level_key,
+        })
+    }
+
+    pub fn parse(&self, line: &str) -> ParsedLine {
+        match self.format {
+            InputFormat::Json => self.parse_json(line).unwrap_or_else(|| self.fallback(line)),
+            InputFormat::Text => self.parse_text(line),
+            InputFormat::Auto => {
+                if line.trim_start().starts_with('{') {
+                    self.parse_json(line).unwrap_or_else(|| self.parse_text(line))
+                } else {
+                    self.parse_text(line)
+                }
+            }
+        }
+    }
+
+    fn parse_text(&self, line: &str) -> ParsedLine {
+        if let Some(re) = &self.pattern {
+            if let Some(caps) = re.captures(line) {
+                let timestamp = caps.name("ts").map(|m| m.as_str().to_string());
+                let target = caps.name("target").map(|m| m.as_str().to_string());
+                let message = caps
+                    .name("msg")
+                    .map(|m| m.as_str().to_string())
+                    .unwrap_or_else(|| line.to_string());
+                // Only take the level from the pattern's own capture group.
+                // Falling back to the whole-line heuristic here would
+                // reintroduce the substring-scan false positives (e.g. a
+                // message body containing "error") that --pattern exists to
+                // avoid.
+                let level = caps
+                    .name("level")
+                    .and_then(|m| LogLevel::parse_name(m.as_str()));
+
+                return ParsedLine {
+                    timestamp,
+                    level,
+                    target: target.or_else(|| directives::extract_target(line)),
+                    message,
+                };
+            }
+        }
+
+        self.fallback(line)
+    }
+
+    /// Decode `line` as a single JSON object and pull out its fields,
+    /// reading the level from `self.level_key` (default `level`). Returns
+    /// `None` for lines that aren't a JSON object, so callers can fall back.
+    fn parse_json(&self, line: &str) -> Option<ParsedLine> {
+        let value: serde_json::Value = serde_json::from_str(line).ok()?;
+        let obj = value.as_object()?;
+
+        let level = obj
+            .get(self.level_key.as_str())
+            .and_then(|v| v.as_str())
+            .and_then(LogLevel::parse_name);
+        let target = obj
+            .get("target")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let timestamp = obj
+            .get("ts")
+            .or_else(|| obj.get("timestamp"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let message = obj
+            .get("msg")
+            .or_else(|| obj.get("message"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| line.to_string());
+
+        Some(ParsedLine {
+            timestamp,
+            level,
+            target,
+            message,
+        })
+    }
+
+    fn fallback(&self, line: &str) -> ParsedLine {
+        ParsedLine {
+            timestamp: None,
+            level: level::parse_log_level(line),
+            target: directives::extract_target(line),
+            message: line.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_without_pattern_uses_the_whole_line_heuristic() {
+        let parser = LineParser::new(InputFormat::Text, None, "level".to_string()).unwrap();
+        let parsed = parser.parse("2026-01-01 ERROR something broke");
+        assert_eq!(parsed.level, Some(LogLevel::Error));
+    }
+
+    #[test]
+    fn pattern_match_with_level_group_uses_only_that_group() {
+        let parser = LineParser::new(
+            InputFormat::Text,
+            Some(r"^(?P<ts>\S+ \S+) (?P<level>\w+) (?P<target>[\w:]+): (?P<msg>.*)$"),
+            "level".to_string(),
+        )
+        .unwrap();
+        let parsed = parser.parse("2026-01-01 10:00:00 INFO mymod::net: error seen upstream");
+        assert_eq!(parsed.level, Some(LogLevel::Info));
+        assert_eq!(parsed.message, "error seen upstream");
+    }
+
+    #[test]
+    fn pattern_match_without_level_group_does_not_fall_back_to_the_heuristic() {
+        let parser = LineParser::new(
+            InputFormat::Text,
+            Some(r"^(?P<ts>\S+ \S+) (?P<target>\S+) (?P<msg>.*)$"),
+            "level".to_string(),
+        )
+        .unwrap();
+        let parsed = parser.parse("2026-01-01 10:00:00 mymod this message mentions error");
+        assert_eq!(parsed.level, None);
+    }
+
+    #[test]
+    fn pattern_miss_falls_back_to_the_heuristic() {
+        let parser = LineParser::new(
+            InputFormat::Text,
+            Some(r"^(?P<ts>\S+ \S+) (?P<level>\w+): (?P<msg>.*)$"),
+            "level".to_string(),
+        )
+        .unwrap();
+        let parsed = parser.parse("this line does not match the pattern ERROR");
+        assert_eq!(parsed.level, Some(LogLevel::Error));
+    }
+}
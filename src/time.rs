@@ -0,0 +1,173 @@
+use crate::level::LogLevel;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use colored::*;
+use std::collections::{BTreeMap, HashMap};
+
+/// Common log timestamp formats to try after RFC3339, all assumed to be UTC.
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y/%m/%d %H:%M:%S",
+];
+
+/// Parse a timestamp extracted from a log line or given on the command
+/// line, trying RFC3339 first and then a handful of common log formats.
+pub fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    NAIVE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .map(|naive| Utc.from_utc_datetime(&naive))
+}
+
+/// Parse a duration like "5m", "1h", "30s", "2d" as used by `--bucket`.
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let invalid = || anyhow!("Invalid duration {s:?}, expected e.g. \"5m\", \"1h\", \"30s\", \"2d\"");
+    if s.len() < 2 {
+        return Err(invalid());
+    }
+    let (value, unit) = s.split_at(s.len() - 1);
+    let value: i64 = value.parse().map_err(|_| invalid())?;
+
+    match unit {
+        "s" => Duration::try_seconds(value).ok_or_else(invalid),
+        "m" => Duration::try_minutes(value).ok_or_else(invalid),
+        "h" => Duration::try_hours(value).ok_or_else(invalid),
+        "d" => Duration::try_days(value).ok_or_else(invalid),
+        _ => Err(invalid()),
+    }
+}
+
+/// An optional `--since`/`--until` window.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimeRange {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    pub fn is_active(&self) -> bool {
+        self.since.is_some() || self.until.is_some()
+    }
+
+    /// Lines without a parsed timestamp are always kept: there's nothing to
+    /// filter on, so the range shouldn't silently drop them.
+    pub fn admits(&self, timestamp: Option<DateTime<Utc>>) -> bool {
+        let Some(ts) = timestamp else {
+            return true;
+        };
+        self.since.is_none_or(|since| ts >= since) && self.until.is_none_or(|until| ts <= until)
+    }
+}
+
+/// Groups matched lines into contiguous, fixed-size time buckets and keeps
+/// a per-level count for each one, ordered by bucket start.
+pub struct Bucketer {
+    duration: Duration,
+    buckets: BTreeMap<DateTime<Utc>, HashMap<LogLevel, usize>>,
+}
+
+impl Bucketer {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, timestamp: DateTime<Utc>, level: Option<LogLevel>) {
+        let counts = self.buckets.entry(self.floor(timestamp)).or_default();
+        if let Some(level) = level {
+            *counts.entry(level).or_insert(0) += 1;
+        }
+    }
+
+    fn floor(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let bucket_secs = self.duration.num_seconds().max(1);
+        let epoch_secs = timestamp.timestamp();
+        let floored = epoch_secs - epoch_secs.rem_euclid(bucket_secs);
+        Utc.timestamp_opt(floored, 0).single().unwrap_or(timestamp)
+    }
+
+    /// Render a per-bucket breakdown with a bar proportional to the
+    /// bucket's total count.
+    pub fn render(&self) {
+        if self.buckets.is_empty() {
+            return;
+        }
+
+        const BAR_WIDTH: usize = 40;
+        println!("\n{}", "Time Buckets:".bold());
+
+        let max_count = self
+            .buckets
+            .values()
+            .map(|counts| counts.values().sum::<usize>())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        for (bucket_start, counts) in &self.buckets {
+            let total: usize = counts.values().sum();
+            let bar_len = (total * BAR_WIDTH / max_count).max(usize::from(total > 0));
+            let bar = "#".repeat(bar_len);
+
+            let mut by_level: Vec<_> = counts.iter().collect();
+            by_level.sort_by(|a, b| b.1.cmp(a.1));
+            let breakdown: Vec<String> = by_level
+                .iter()
+                .map(|(level, count)| format!("{level:?}:{count}"))
+                .collect();
+
+            println!(
+                "  {} | {bar:<BAR_WIDTH$} {total:>4}  [{}]",
+                bucket_start.to_rfc3339(),
+                breakdown.join(", "),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_accepts_each_unit() {
+        assert_eq!(parse_duration("30s").unwrap(), Duration::seconds(30));
+        assert_eq!(parse_duration("5m").unwrap(), Duration::minutes(5));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::hours(1));
+        assert_eq!(parse_duration("2d").unwrap(), Duration::days(2));
+    }
+
+    #[test]
+    fn parse_duration_rejects_malformed_input() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("5").is_err());
+        assert!(parse_duration("5x").is_err());
+        assert!(parse_duration("abcm").is_err());
+    }
+
+    #[test]
+    fn parse_duration_rejects_out_of_range_values_instead_of_panicking() {
+        assert!(parse_duration("999999999999999999d").is_err());
+        assert!(parse_duration("999999999999999999999m").is_err());
+    }
+
+    #[test]
+    fn bucketer_floors_to_the_bucket_boundary() {
+        let bucketer = Bucketer::new(Duration::minutes(5));
+        let ts = DateTime::parse_from_rfc3339("2026-01-01T10:07:42Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let floored = bucketer.floor(ts);
+
+        assert_eq!(floored.to_rfc3339(), "2026-01-01T10:05:00+00:00");
+    }
+}